@@ -1,13 +1,20 @@
 //! Unix pipe types.
+//!
+//! [`Sender`] and [`Receiver`] always register with mio, which normalizes
+//! registrations to edge-triggered semantics across platforms. There is no
+//! per-pipe "level-triggered" mode to opt into: re-arming happens once per
+//! `WouldBlock`, not once per syscall, so the overhead under sustained load
+//! is already minimal.
 
 use crate::io::interest::Interest;
+use crate::io::unix::AsyncFd;
 use crate::io::{AsyncRead, AsyncWrite, PollEvented, ReadBuf, Ready};
 
 use mio::unix::pipe as mio_pipe;
 use std::fs::File;
 use std::io::{self, Read, Write};
 use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
-use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, RawFd};
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
 use std::path::Path;
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -225,6 +232,7 @@ impl OpenOptions {
 
         Ok(file)
     }
+
 }
 
 impl Default for OpenOptions {
@@ -314,6 +322,21 @@ pub struct Sender {
     io: PollEvented<mio_pipe::Sender>,
 }
 
+/// Pipe buffer capacity information, as reported by [`Sender::capacity`].
+#[cfg(target_os = "linux")]
+#[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+#[derive(Debug, Clone, Copy)]
+pub struct PipeCapacity {
+    /// The pipe's current buffer capacity, in bytes, as reported by `fcntl(F_GETPIPE_SZ)`.
+    pub current: usize,
+    /// The largest capacity [`Sender::set_pipe_size`] can grant, in bytes, as reported
+    /// by `/proc/sys/fs/pipe-max-size`.
+    pub max: usize,
+    /// The system page size, in bytes. The kernel rounds both `current` and `max` up
+    /// to a multiple of this.
+    pub page_size: usize,
+}
+
 impl Sender {
     fn from_mio(mio_tx: mio_pipe::Sender) -> io::Result<Sender> {
         let io = PollEvented::new_with_interest(mio_tx, Interest::WRITABLE)?;
@@ -400,6 +423,64 @@ impl Sender {
         Sender::from_mio(mio_tx)
     }
 
+    /// Creates a new `Sender` from an owned file descriptor.
+    ///
+    /// This is a safe alternative to [`from_file_unchecked`] for adopting a file
+    /// descriptor that was not obtained by opening a path, e.g. one inherited from
+    /// a child process or received over a Unix socket with `SCM_RIGHTS`. It will
+    /// check that the descriptor refers to a pipe and has write access, set it in
+    /// non-blocking mode and perform the conversion.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `io::ErrorKind::InvalidInput` if the descriptor is not a pipe or
+    /// it does not have write access. Also fails with any standard OS error if it
+    /// occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is not called from within a runtime with
+    /// IO enabled.
+    ///
+    /// The runtime is usually set implicitly when this function is called
+    /// from a future driven by a tokio runtime, otherwise runtime can be set
+    /// explicitly with [`Runtime::enter`](crate::runtime::Runtime::enter) function.
+    ///
+    /// [`from_file_unchecked`]: Self::from_file_unchecked
+    pub fn from_owned_fd(fd: OwnedFd) -> io::Result<Sender> {
+        Sender::from_file(File::from(fd))
+    }
+
+    /// Creates a new `Sender` from a [`File`] already registered as an [`AsyncFd`].
+    ///
+    /// This deregisters the file descriptor from `afd`'s registration and hands
+    /// it to [`from_file`], which checks that the file is a pipe and has write
+    /// access, sets it in non-blocking mode and re-registers it with the I/O
+    /// driver. The `AsyncFd`'s registration does not carry over: this always
+    /// re-registers, so any in-flight readiness waiters on `afd` are dropped
+    /// along with it.
+    ///
+    /// [`AsyncFd`]: crate::io::unix::AsyncFd
+    /// [`from_file`]: Self::from_file
+    ///
+    /// # Errors
+    ///
+    /// Fails with `io::ErrorKind::InvalidInput` if the file is not a pipe or it
+    /// does not have write access. Also fails with any standard OS error if it
+    /// occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is not called from within a runtime with
+    /// IO enabled.
+    ///
+    /// The runtime is usually set implicitly when this function is called
+    /// from a future driven by a tokio runtime, otherwise runtime can be set
+    /// explicitly with [`Runtime::enter`](crate::runtime::Runtime::enter) function.
+    pub fn from_async_fd(afd: AsyncFd<File>) -> io::Result<Sender> {
+        Sender::from_file(afd.into_inner())
+    }
+
     /// Waits for any of the requested ready states.
     ///
     /// This function can be used instead of [`writable()`] to check the returned
@@ -431,6 +512,10 @@ impl Sender {
     ///
     /// [`try_write()`]: Self::try_write
     ///
+    /// Note that on multiple calls to a `poll_*` method in the write direction,
+    /// only the `Waker` from the `Context` passed to the most recent call will
+    /// be scheduled to receive a wakeup.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -624,6 +709,148 @@ impl Sender {
             .registration()
             .try_io(Interest::WRITABLE, || (&*self.io).write_vectored(buf))
     }
+
+    /// Tries to write an entire buffer to the pipe without awaiting, looping
+    /// over [`try_write()`] until the buffer is exhausted or a write would
+    /// block.
+    ///
+    /// Unlike [`try_write()`], this method keeps calling `try_write` for as
+    /// long as it keeps making progress. It never awaits, so it is suitable
+    /// for callers that want to implement their own backpressure policy
+    /// instead of awaiting [`writable()`]. If `buf` is not longer than
+    /// `PIPE_BUF` and the pipe has enough free space, the whole buffer is
+    /// typically written in one atomic `try_write` call.
+    ///
+    /// [`try_write()`]: Self::try_write()
+    /// [`writable()`]: Self::writable()
+    ///
+    /// # Return
+    ///
+    /// Returns `Ok(n)` with the number of bytes written, where `n` may be
+    /// less than `buf.len()` if a subsequent write would have blocked. If the
+    /// very first write would block, `Ok(0)` is returned rather than an
+    /// error, so callers don't need to special-case an empty write.
+    ///
+    /// # Errors
+    ///
+    /// If any write fails with an error other than `WouldBlock`, that error
+    /// is returned immediately, even if some bytes were already written.
+    pub fn try_write_all(&self, buf: &[u8]) -> io::Result<usize> {
+        let mut written = 0;
+        while written < buf.len() {
+            match self.try_write(&buf[written..]) {
+                Ok(n) => written += n,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(written)
+    }
+
+    /// Checks whether the pipe's buffer has no free space left for writing.
+    ///
+    /// This compares the pipe's capacity (as reported by `fcntl(F_GETPIPE_SZ)`)
+    /// against the number of bytes currently queued in the buffer (as
+    /// reported by `ioctl(FIONREAD)`). It does not perform any I/O and does
+    /// not block.
+    ///
+    /// Note that the result is inherently racy: another process or task may
+    /// read from or write to the pipe concurrently, so the returned value
+    /// should only be used as a hint.
+    ///
+    /// # Platform support
+    ///
+    /// This function relies on `F_GETPIPE_SZ`, which is only available on
+    /// Linux.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn is_full(&self) -> io::Result<bool> {
+        let fd = self.as_raw_fd();
+        let capacity = get_pipe_size(fd)?;
+        let queued = fionread(fd)?;
+        Ok(queued >= capacity)
+    }
+
+    /// Resizes the pipe's kernel buffer and returns the capacity that was
+    /// actually granted.
+    ///
+    /// There is no portable way to translate an expected throughput into a
+    /// buffer size, so unlike a hypothetical `hint_throughput`, this takes
+    /// the desired capacity directly: callers that want to size the buffer
+    /// for an expected throughput should pick a multiple of the send rate
+    /// and a tolerable latency budget themselves. The requested size is
+    /// clamped to the system-wide limit in `/proc/sys/fs/pipe-max-size`
+    /// (falling back to no limit if that file cannot be read), and the
+    /// kernel rounds the result up to a page size.
+    ///
+    /// # Platform support
+    ///
+    /// This function relies on `F_SETPIPE_SZ`, which is only available on
+    /// Linux.
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn set_pipe_size(&self, size: usize) -> io::Result<usize> {
+        set_pipe_size(self.as_raw_fd(), size)
+    }
+
+    /// Reports the pipe's current buffer capacity alongside the kernel-enforced limits.
+    ///
+    /// This combines `fcntl(F_GETPIPE_SZ)`, the system-wide limit in
+    /// `/proc/sys/fs/pipe-max-size`, and the system page size into a single
+    /// call, since sizing a protocol around the pipe buffer typically needs
+    /// all three together rather than one at a time via [`set_pipe_size`].
+    ///
+    /// # Platform support
+    ///
+    /// This function relies on `F_GETPIPE_SZ`, which is only available on
+    /// Linux.
+    ///
+    /// [`set_pipe_size`]: Self::set_pipe_size
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn capacity(&self) -> io::Result<PipeCapacity> {
+        Ok(PipeCapacity {
+            current: get_pipe_size(self.as_raw_fd())?,
+            max: pipe_max_size(),
+            page_size: page_size()?,
+        })
+    }
+
+    /// Deregisters this `Sender` from the reactor and converts it into a
+    /// [`std::process::Stdio`], suitable for use as a child process's stdout
+    /// or stderr.
+    ///
+    /// The underlying file descriptor is cleared of `O_NONBLOCK` first,
+    /// since most child processes do not expect to inherit a non-blocking
+    /// stdio handle.
+    ///
+    /// # Errors
+    ///
+    /// Fails if deregistering from the reactor or clearing `O_NONBLOCK`
+    /// fails.
+    pub fn into_stdio(self) -> io::Result<std::process::Stdio> {
+        into_stdio(self.io)
+    }
+
+    /// Deregisters this `Sender` from the reactor and converts it into a
+    /// blocking [`File`], for handing the pipe to code that expects std's
+    /// blocking [`Write`] (and [`Read`]) implementations.
+    ///
+    /// The underlying file descriptor is cleared of `O_NONBLOCK` first, since
+    /// std's [`Write`] does not special-case `WouldBlock`. To convert back
+    /// into a `Sender`, use [`from_file`], which re-registers it and sets
+    /// `O_NONBLOCK` again.
+    ///
+    /// [`from_file`]: Self::from_file
+    ///
+    /// # Errors
+    ///
+    /// Fails if deregistering from the reactor or clearing `O_NONBLOCK`
+    /// fails.
+    pub fn into_blocking(self) -> io::Result<File> {
+        into_blocking(self.io)
+    }
+
 }
 
 impl AsyncWrite for Sender {
@@ -826,6 +1053,64 @@ impl Receiver {
         Receiver::from_mio(mio_rx)
     }
 
+    /// Creates a new `Receiver` from an owned file descriptor.
+    ///
+    /// This is a safe alternative to [`from_file_unchecked`] for adopting a file
+    /// descriptor that was not obtained by opening a path, e.g. one inherited from
+    /// a child process or received over a Unix socket with `SCM_RIGHTS`. It will
+    /// check that the descriptor refers to a pipe and has read access, set it in
+    /// non-blocking mode and perform the conversion.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `io::ErrorKind::InvalidInput` if the descriptor is not a pipe or
+    /// it does not have read access. Also fails with any standard OS error if it
+    /// occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is not called from within a runtime with
+    /// IO enabled.
+    ///
+    /// The runtime is usually set implicitly when this function is called
+    /// from a future driven by a tokio runtime, otherwise runtime can be set
+    /// explicitly with [`Runtime::enter`](crate::runtime::Runtime::enter) function.
+    ///
+    /// [`from_file_unchecked`]: Self::from_file_unchecked
+    pub fn from_owned_fd(fd: OwnedFd) -> io::Result<Receiver> {
+        Receiver::from_file(File::from(fd))
+    }
+
+    /// Creates a new `Receiver` from a [`File`] already registered as an [`AsyncFd`].
+    ///
+    /// This deregisters the file descriptor from `afd`'s registration and hands
+    /// it to [`from_file`], which checks that the file is a pipe and has read
+    /// access, sets it in non-blocking mode and re-registers it with the I/O
+    /// driver. The `AsyncFd`'s registration does not carry over: this always
+    /// re-registers, so any in-flight readiness waiters on `afd` are dropped
+    /// along with it.
+    ///
+    /// [`AsyncFd`]: crate::io::unix::AsyncFd
+    /// [`from_file`]: Self::from_file
+    ///
+    /// # Errors
+    ///
+    /// Fails with `io::ErrorKind::InvalidInput` if the file is not a pipe or it
+    /// does not have read access. Also fails with any standard OS error if it
+    /// occurs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is not called from within a runtime with
+    /// IO enabled.
+    ///
+    /// The runtime is usually set implicitly when this function is called
+    /// from a future driven by a tokio runtime, otherwise runtime can be set
+    /// explicitly with [`Runtime::enter`](crate::runtime::Runtime::enter) function.
+    pub fn from_async_fd(afd: AsyncFd<File>) -> io::Result<Receiver> {
+        Receiver::from_file(afd.into_inner())
+    }
+
     /// Waits for any of the requested ready states.
     ///
     /// This function can be used instead of [`readable()`] to check the returned
@@ -857,6 +1142,10 @@ impl Receiver {
     ///
     /// [`try_read()`]: Self::try_read()
     ///
+    /// Note that on multiple calls to a `poll_*` method in the read direction,
+    /// only the `Waker` from the `Context` passed to the most recent call will
+    /// be scheduled to receive a wakeup.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -930,6 +1219,50 @@ impl Receiver {
         self.io.registration().poll_read_ready(cx).map_ok(|_| ())
     }
 
+    /// Polls for `buf` to be completely filled, for use in hand-rolled state
+    /// machines that cannot own a pinned future.
+    ///
+    /// This behaves like a `poll`-shaped [`AsyncReadExt::read_exact`]: it
+    /// keeps filling `buf` until there is no remaining space, returning
+    /// `Poll::Pending` (after registering the waker) if the pipe would block
+    /// partway through.
+    ///
+    /// # Contract
+    ///
+    /// The caller must pass the *same* `buf` on every call until this
+    /// returns `Poll::Ready`, since progress is tracked via `buf`'s filled
+    /// cursor, not internal state on `Receiver`.
+    ///
+    /// [`AsyncReadExt::read_exact`]: crate::io::AsyncReadExt::read_exact
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind `UnexpectedEof` if the writing end closes
+    /// before `buf` is completely filled.
+    pub fn poll_read_exact(
+        &self,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        while buf.remaining() > 0 {
+            let filled_before = buf.filled().len();
+            // Safety: `mio_pipe::Receiver` uses a `std::fs::File` underneath,
+            // which correctly handles reads into uninitialized memory.
+            match unsafe { self.io.poll_read(cx, buf) } {
+                Poll::Ready(Ok(())) => {
+                    if buf.filled().len() == filled_before {
+                        return Poll::Ready(Err(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "early eof while filling the buffer",
+                        )));
+                    }
+                }
+                other => return other,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
     /// Tries to read data from the pipe into the provided buffer, returning how
     /// many bytes were read.
     ///
@@ -1147,6 +1480,118 @@ impl Receiver {
             })
         }
     }
+
+    /// Checks whether the pipe's buffer has no data queued for reading.
+    ///
+    /// This is based on `ioctl(FIONREAD)` and does not perform any I/O and
+    /// does not block.
+    ///
+    /// Note that the result is inherently racy: a writer may add data to the
+    /// pipe concurrently, so the returned value should only be used as a
+    /// hint, e.g. to decide whether calling [`try_read()`] is worthwhile.
+    ///
+    /// [`try_read()`]: Self::try_read()
+    pub fn is_empty(&self) -> io::Result<bool> {
+        Ok(fionread(self.as_raw_fd())? == 0)
+    }
+
+    /// Reads and discards any data currently queued in the pipe.
+    ///
+    /// This is useful for resynchronizing after a protocol error, by
+    /// flushing stale bytes before reading a fresh message. It repeatedly
+    /// calls [`try_read()`] into a scratch buffer until it hits
+    /// `WouldBlock` or EOF; it never waits for more data to arrive, so it
+    /// returns promptly even if the pipe is not empty afterwards.
+    ///
+    /// [`try_read()`]: Self::try_read()
+    ///
+    /// # Return
+    ///
+    /// Returns the number of bytes discarded.
+    pub async fn drain(&self) -> io::Result<usize> {
+        let mut scratch = [0; 4096];
+        let mut discarded = 0;
+        loop {
+            match self.try_read(&mut scratch) {
+                Ok(0) => break,
+                Ok(n) => discarded += n,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(discarded)
+    }
+
+    /// Reads the exact number of bytes required to fill `bufs`, awaiting
+    /// readiness as needed and scattering the data across the buffers in
+    /// order.
+    ///
+    /// This is the vectored, owned-buffer counterpart to
+    /// [`AsyncReadExt::read_exact`]; it advances across slice boundaries as
+    /// they fill up, so a payload may be split over a header buffer and a
+    /// body buffer, for example.
+    ///
+    /// [`AsyncReadExt::read_exact`]: crate::io::AsyncReadExt::read_exact
+    ///
+    /// # Errors
+    ///
+    /// If the pipe's writing end closes before `bufs` is completely filled,
+    /// this function returns an error of kind `UnexpectedEof`.
+    pub async fn read_exact_vectored(
+        &self,
+        mut bufs: &mut [io::IoSliceMut<'_>],
+    ) -> io::Result<()> {
+        advance_slices(&mut bufs, 0);
+        while !bufs.is_empty() {
+            self.readable().await?;
+            match self.try_read_vectored(bufs) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "early eof while filling vectored buffers",
+                    ))
+                }
+                Ok(n) => advance_slices(&mut bufs, n),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Deregisters this `Receiver` from the reactor and converts it into a
+    /// [`std::process::Stdio`], suitable for use as a child process's stdin.
+    ///
+    /// The underlying file descriptor is cleared of `O_NONBLOCK` first,
+    /// since most child processes do not expect to inherit a non-blocking
+    /// stdio handle.
+    ///
+    /// # Errors
+    ///
+    /// Fails if deregistering from the reactor or clearing `O_NONBLOCK`
+    /// fails.
+    pub fn into_stdio(self) -> io::Result<std::process::Stdio> {
+        into_stdio(self.io)
+    }
+
+    /// Deregisters this `Receiver` from the reactor and converts it into a
+    /// blocking [`File`], for handing the pipe to code that expects std's
+    /// blocking [`Read`] (and [`Write`]) implementations.
+    ///
+    /// The underlying file descriptor is cleared of `O_NONBLOCK` first, since
+    /// std's [`Read`] does not special-case `WouldBlock`. To convert back
+    /// into a `Receiver`, use [`from_file`], which re-registers it and sets
+    /// `O_NONBLOCK` again.
+    ///
+    /// [`from_file`]: Self::from_file
+    ///
+    /// # Errors
+    ///
+    /// Fails if deregistering from the reactor or clearing `O_NONBLOCK`
+    /// fails.
+    pub fn into_blocking(self) -> io::Result<File> {
+        into_blocking(self.io)
+    }
 }
 
 impl AsyncRead for Receiver {
@@ -1173,11 +1618,134 @@ impl AsFd for Receiver {
     }
 }
 
+/// Advances a slice of `IoSliceMut`s by `n` bytes, dropping fully consumed
+/// buffers from the front and shrinking a partially consumed one.
+///
+/// This mirrors the standard library's (currently unstable on our MSRV)
+/// `IoSliceMut::advance_slices`.
+fn advance_slices(bufs: &mut &mut [io::IoSliceMut<'_>], n: usize) {
+    let mut remove = 0;
+    let mut left = n;
+    for buf in bufs.iter() {
+        if left < buf.len() {
+            break;
+        }
+        left -= buf.len();
+        remove += 1;
+    }
+
+    *bufs = &mut std::mem::take(bufs)[remove..];
+    if !bufs.is_empty() && left > 0 {
+        let first = &mut bufs[0];
+        let ptr = first.as_mut_ptr();
+        let len = first.len();
+        // Safety: `ptr` points at `len` initialized bytes borrowed from the
+        // original slice; shrinking the view from the front by `left` bytes
+        // stays within that same allocation and preserves the borrow's
+        // lifetime, since `first`'s old borrow has already ended by the time
+        // the new one is constructed.
+        let shrunk = unsafe { std::slice::from_raw_parts_mut(ptr.add(left), len - left) };
+        bufs[0] = io::IoSliceMut::new(shrunk);
+    }
+}
+
+/// Deregisters a pipe end from the reactor, clears `O_NONBLOCK` and converts
+/// it into a blocking [`std::process::Stdio`] handle.
+fn into_stdio<E>(io: PollEvented<E>) -> io::Result<std::process::Stdio>
+where
+    E: mio::event::Source + IntoRawFd,
+{
+    Ok(std::process::Stdio::from(OwnedFd::from(into_blocking(io)?)))
+}
+
+/// Deregisters a pipe end from the reactor, clears `O_NONBLOCK` and converts
+/// it into a blocking [`File`], ready to be handed to code that expects
+/// std's blocking [`Read`]/[`Write`] traits.
+fn into_blocking<E>(io: PollEvented<E>) -> io::Result<File>
+where
+    E: mio::event::Source + IntoRawFd,
+{
+    let mio_io = io.into_inner()?;
+    let owned_fd = unsafe { OwnedFd::from_raw_fd(mio_io.into_raw_fd()) };
+    let raw_fd = owned_fd.as_raw_fd();
+
+    let flags = unsafe { libc::fcntl(raw_fd, libc::F_GETFL) };
+    if flags < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if flags & libc::O_NONBLOCK != 0 {
+        let ret = unsafe { libc::fcntl(raw_fd, libc::F_SETFL, flags & !libc::O_NONBLOCK) };
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    Ok(File::from(owned_fd))
+}
+
 /// Checks if file is a FIFO
 fn is_fifo(file: &File) -> io::Result<bool> {
     Ok(file.metadata()?.file_type().is_fifo())
 }
 
+/// Returns the number of bytes currently queued in the pipe, via `ioctl(FIONREAD)`.
+fn fionread(fd: RawFd) -> io::Result<usize> {
+    let mut queued: libc::c_int = 0;
+    let ret = unsafe { libc::ioctl(fd, libc::FIONREAD, &mut queued) };
+    if ret < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(queued as usize)
+    }
+}
+
+/// Returns the pipe's buffer capacity, via `fcntl(F_GETPIPE_SZ)`.
+#[cfg(target_os = "linux")]
+fn get_pipe_size(fd: RawFd) -> io::Result<usize> {
+    let ret = unsafe { libc::fcntl(fd, libc::F_GETPIPE_SZ) };
+    if ret < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret as usize)
+    }
+}
+
+/// Sets the pipe's buffer capacity via `fcntl(F_SETPIPE_SZ)`, clamped to the
+/// system-wide limit in `/proc/sys/fs/pipe-max-size`, and returns the actual
+/// capacity the kernel granted (which is rounded up to a page size).
+#[cfg(target_os = "linux")]
+fn set_pipe_size(fd: RawFd, size: usize) -> io::Result<usize> {
+    let size = size.min(pipe_max_size()).min(libc::c_int::MAX as usize);
+
+    let ret = unsafe { libc::fcntl(fd, libc::F_SETPIPE_SZ, size as libc::c_int) };
+    if ret < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        get_pipe_size(fd)
+    }
+}
+
+/// Returns the system-wide limit on a pipe's buffer capacity, from
+/// `/proc/sys/fs/pipe-max-size`, falling back to no limit if it cannot be read.
+#[cfg(target_os = "linux")]
+fn pipe_max_size() -> usize {
+    std::fs::read_to_string("/proc/sys/fs/pipe-max-size")
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .unwrap_or(usize::MAX)
+}
+
+/// Returns the system page size, via `sysconf(_SC_PAGESIZE)`.
+#[cfg(target_os = "linux")]
+fn page_size() -> io::Result<usize> {
+    let ret = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if ret < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret as usize)
+    }
+}
+
 /// Gets file descriptor's flags by fcntl.
 fn get_file_flags(file: &File) -> io::Result<libc::c_int> {
     let fd = file.as_raw_fd();
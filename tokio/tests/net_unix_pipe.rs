@@ -9,7 +9,7 @@ use tokio_test::{assert_err, assert_ok, assert_pending, assert_ready_ok};
 use std::fs::File;
 use std::io;
 use std::os::unix::fs::OpenOptionsExt;
-use std::os::unix::io::AsRawFd;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
 use std::path::{Path, PathBuf};
 
 /// Helper struct which will clean up temporary files once dropped.
@@ -341,6 +341,326 @@ async fn try_read_write() -> io::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn try_write_all() -> io::Result<()> {
+    const DATA: &[u8] = b"this is some data to write to the fifo";
+
+    let fifo = TempFifo::new("try_write_all")?;
+    let reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    // A small write fits into the pipe buffer in one shot.
+    writer.writable().await?;
+    let n = writer.try_write_all(DATA)?;
+    assert_eq!(n, DATA.len());
+
+    let mut read_data = vec![0; DATA.len()];
+    reader.readable().await?;
+    let read = reader.try_read(&mut read_data)?;
+    assert_eq!(&read_data[..read], DATA);
+
+    // Fill the pipe buffer, then `try_write_all` should stop early and
+    // report only the bytes it actually managed to write.
+    let mut total = 0;
+    while writable_by_poll(&writer) {
+        match writer.try_write_all(DATA) {
+            Ok(0) => break,
+            Ok(n) => total += n,
+            Err(e) => return Err(e),
+        }
+    }
+    assert!(total > 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn try_write_all_broken_pipe() -> io::Result<()> {
+    let fifo = TempFifo::new("try_write_all_broken_pipe")?;
+    let reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+    writer.writable().await?;
+    drop(reader);
+
+    let err = writer.try_write_all(b"hello").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(target_os = "linux")]
+async fn is_empty_and_is_full() -> io::Result<()> {
+    let fifo = TempFifo::new("is_empty_and_is_full")?;
+    let reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    assert!(reader.is_empty()?);
+    assert!(!writer.is_full()?);
+
+    // A single write of exactly the pipe's capacity, issued against an empty
+    // pipe, is guaranteed to fill it completely: `write(2)` on a pipe only
+    // has to be atomic for requests up to `PIPE_BUF`, so a larger request is
+    // simply filled as far as there is room for.
+    let capacity = unsafe { libc::fcntl(writer.as_raw_fd(), libc::F_GETPIPE_SZ) };
+    assert!(capacity > 0);
+    let data = vec![0u8; capacity as usize];
+
+    writer.writable().await?;
+    let n = writer.try_write(&data)?;
+    assert_eq!(n, data.len());
+    assert!(!reader.is_empty()?);
+    assert!(writer.is_full()?);
+
+    // Drain it again.
+    let mut read_data = vec![0; data.len()];
+    let mut i = 0;
+    while i < read_data.len() {
+        reader.readable().await?;
+        match reader.try_read(&mut read_data[i..]) {
+            Ok(n) => i += n,
+            Err(e) => {
+                assert_eq!(e.kind(), io::ErrorKind::WouldBlock);
+                continue;
+            }
+        }
+    }
+    assert!(reader.is_empty()?);
+    assert!(!writer.is_full()?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn drain() -> io::Result<()> {
+    const GARBAGE: &[u8] = b"stale bytes from a previous, out-of-sync message";
+    const MESSAGE: &[u8] = b"a well-formed message";
+
+    let fifo = TempFifo::new("drain")?;
+    let mut reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    writer.writable().await?;
+    writer.try_write(GARBAGE)?;
+
+    reader.readable().await?;
+    let discarded = reader.drain().await?;
+    assert_eq!(discarded, GARBAGE.len());
+
+    // The pipe is now empty, so draining again finds nothing to discard.
+    assert_eq!(reader.drain().await?, 0);
+
+    // A subsequent, well-formed message reads cleanly.
+    writer.writable().await?;
+    writer.try_write(MESSAGE)?;
+    let mut buf = vec![0; MESSAGE.len()];
+    reader.read_exact(&mut buf).await?;
+    assert_eq!(buf, MESSAGE);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_exact_vectored() -> io::Result<()> {
+    const HEADER: &[u8] = b"HDR1";
+    const BODY: &[u8] = b"the rest of the message";
+
+    let fifo = TempFifo::new("read_exact_vectored")?;
+    let reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    writer.writable().await?;
+    let mut payload = HEADER.to_vec();
+    payload.extend_from_slice(BODY);
+    writer.try_write(&payload)?;
+
+    let mut header = [0; HEADER.len()];
+    let mut body = [0; BODY.len()];
+    let mut bufs = [
+        io::IoSliceMut::new(&mut header),
+        io::IoSliceMut::new(&mut body),
+    ];
+    reader.read_exact_vectored(&mut bufs).await?;
+
+    assert_eq!(&header, HEADER);
+    assert_eq!(&body, BODY);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_exact_vectored_early_eof() -> io::Result<()> {
+    let fifo = TempFifo::new("read_exact_vectored_early_eof")?;
+    let reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    writer.writable().await?;
+    writer.try_write(b"short")?;
+    drop(writer);
+
+    let mut a = [0; 5];
+    let mut b = [0; 5];
+    let mut bufs = [io::IoSliceMut::new(&mut a), io::IoSliceMut::new(&mut b)];
+    let err = reader.read_exact_vectored(&mut bufs).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(target_os = "linux")]
+async fn set_pipe_size() -> io::Result<()> {
+    let fifo = TempFifo::new("set_pipe_size")?;
+    let _reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    let granted = writer.set_pipe_size(1024 * 1024)?;
+    // `set_pipe_size` clamps to `/proc/sys/fs/pipe-max-size` and the kernel
+    // rounds to a page size, so we can't assert an exact value, only that
+    // it's sane.
+    assert!(granted > 0);
+
+    let shrunk = writer.set_pipe_size(4096)?;
+    assert!(shrunk <= granted);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(target_os = "linux")]
+async fn capacity() -> io::Result<()> {
+    let fifo = TempFifo::new("capacity")?;
+    let _reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    let cap = writer.capacity()?;
+    assert!(cap.current > 0);
+    assert!(cap.current <= cap.max);
+    assert!(cap.page_size > 0);
+    assert_eq!(cap.current % cap.page_size, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn poll_read_exact() -> io::Result<()> {
+    const DATA: &[u8] = b"this is some data to write to the fifo";
+
+    let fifo = TempFifo::new("poll_read_exact")?;
+    let reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    let mut storage = [0; DATA.len()];
+    let mut buf = tokio::io::ReadBuf::new(&mut storage);
+    let mut read_fut = task::spawn(std::future::poll_fn(move |cx| {
+        reader.poll_read_exact(cx, &mut buf)
+    }));
+    assert_pending!(read_fut.poll());
+
+    writer.writable().await?;
+    writer.try_write(DATA)?;
+
+    while !read_fut.is_woken() {
+        tokio::task::yield_now().await;
+    }
+    assert_ready_ok!(read_fut.poll());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn into_stdio() -> io::Result<()> {
+    const DATA: &[u8] = b"hello from a fifo\n";
+
+    let fifo = TempFifo::new("into_stdio")?;
+    let reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let mut writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    let child = tokio::process::Command::new("cat")
+        .stdin(reader.into_stdio()?)
+        .stdout(std::process::Stdio::piped())
+        .spawn()?;
+
+    writer.write_all(DATA).await?;
+    drop(writer);
+
+    let output = child.wait_with_output().await?;
+    assert_eq!(output.stdout, DATA);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn into_blocking() -> io::Result<()> {
+    const DATA: &[u8] = b"hello from a blocking view\n";
+
+    let fifo = TempFifo::new("into_blocking")?;
+    let reader = pipe::OpenOptions::new().open_receiver(&fifo)?;
+    let writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    // Convert the writer to a blocking `File` and write with std's `Write`.
+    let mut blocking_writer = writer.into_blocking()?;
+    tokio::task::spawn_blocking(move || {
+        use std::io::Write;
+        blocking_writer.write_all(DATA)
+    })
+    .await??;
+
+    // Read the data back asynchronously to confirm the round trip.
+    let mut reader = reader;
+    let mut read_data = vec![0; DATA.len()];
+    reader.read_exact(&mut read_data).await?;
+    assert_eq!(&read_data, DATA);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn from_owned_fd() -> io::Result<()> {
+    const DATA: &[u8] = b"hello from an owned fd\n";
+
+    // Create an anonymous pipe pair with pipe2(2) and adopt both ends, rather
+    // than going through a named FIFO on disk.
+    let (rx_fd, tx_fd) = nix::unistd::pipe2(nix::fcntl::OFlag::empty())?;
+    let mut rx = pipe::Receiver::from_owned_fd(unsafe { OwnedFd::from_raw_fd(rx_fd) })?;
+    let mut tx = pipe::Sender::from_owned_fd(unsafe { OwnedFd::from_raw_fd(tx_fd) })?;
+
+    tx.write_all(DATA).await?;
+    drop(tx);
+
+    let mut buf = [0; DATA.len()];
+    rx.read_exact(&mut buf).await?;
+    assert_eq!(&buf[..], DATA);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn from_async_fd() -> io::Result<()> {
+    const DATA: &[u8] = b"hello from an AsyncFd\n";
+
+    let fifo = TempFifo::new("from_async_fd")?;
+
+    // Simulate a caller that already wrapped the FIFO in a raw `AsyncFd`
+    // before discovering the richer `Receiver` API.
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(&fifo)?;
+    let afd = tokio::io::unix::AsyncFd::new(file)?;
+    let mut reader = pipe::Receiver::from_async_fd(afd)?;
+
+    let mut writer = pipe::OpenOptions::new().open_sender(&fifo)?;
+
+    writer.write_all(DATA).await?;
+
+    let mut buf = [0; DATA.len()];
+    reader.read_exact(&mut buf).await?;
+    assert_eq!(&buf[..], DATA);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn try_read_write_vectored() -> io::Result<()> {
     const DATA: &[u8] = b"this is some data to write to the fifo";